@@ -0,0 +1,31 @@
+//! Custom errors for the CPI_transfer program.
+
+use solana_program::program_error::ProgramError;
+use thiserror::Error;
+
+/// Errors that may be returned by the CPI_transfer program, in addition to the
+/// standard [`ProgramError`] variants.
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum TransferError {
+    /// `Release` was invoked, but no vesting schedule entry is due yet.
+    #[error("No vesting schedule entries are due for release yet")]
+    NothingDue,
+
+    /// A lamport transfer was attempted for more than the source PDA vault holds.
+    #[error("Source account has insufficient funds for the requested transfer")]
+    InsufficientFunds,
+
+    /// Fee or remainder arithmetic overflowed or underflowed.
+    #[error("Fee calculation overflowed")]
+    FeeCalculationOverflow,
+
+    /// The fee destination account's mint doesn't match the transfer's mint.
+    #[error("Fee destination account mint does not match the transfer mint")]
+    FeeMintMismatch,
+}
+
+impl From<TransferError> for ProgramError {
+    fn from(e: TransferError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
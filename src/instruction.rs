@@ -0,0 +1,271 @@
+//! Instruction definitions for the CPI_transfer program.
+
+use {
+    crate::state::ScheduleEntry,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_program, sysvar,
+    },
+    spl_associated_token_account::get_associated_token_address,
+};
+
+/// Instructions supported by the CPI_transfer program.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TransferInstruction {
+    /// Transfer `amount` tokens from the PDA-owned source account to the wallet owner's
+    /// associated token account for the mint, creating that associated token account
+    /// first if it doesn't exist yet. If `fee_bps` is non-zero, a `fee_bps` / 10_000
+    /// cut of `amount` is routed to the fee destination account instead, atomically
+    /// alongside the remainder to the primary destination.
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` Source SPL token account (owned by the PDA authority).
+    /// 1. `[]` Mint account.
+    /// 2. `[writable]` Destination SPL token account (the wallet owner's ATA for the
+    ///    mint; may not exist yet).
+    /// 3. `[]` PDA authority account.
+    /// 4. `[]` SPL Token program.
+    /// 5. `[signer, writable]` Wallet owner (receives and funds creation of the ATA).
+    /// 6. `[]` System program.
+    /// 7. `[]` Associated Token Account program.
+    /// 8. `[]` Rent sysvar.
+    /// 9. `[writable]` Fee destination SPL token account (same mint; ignored if
+    ///    `fee_bps` is zero).
+    Transfer {
+        /// Amount of tokens to transfer, in the mint's smallest unit.
+        amount: u64,
+        /// Basis points of `amount` (1/10_000) to route to the fee destination instead.
+        fee_bps: u16,
+    },
+
+    /// Initialize a vesting schedule data account with an ordered list of release entries.
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` Vesting schedule data account (owned by this program, pre-allocated
+    ///    with at least `VestingSchedule::packed_len(schedule.len())` bytes and not yet
+    ///    initialized).
+    CreateSchedule {
+        /// Ordered vesting entries, by non-decreasing `release_time`.
+        schedule: Vec<ScheduleEntry>,
+    },
+
+    /// Release every vesting entry whose `release_time` has passed, transferring their
+    /// combined amount from the PDA-owned source account to the destination account.
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` Source SPL token account (owned by the PDA authority).
+    /// 1. `[]` Mint account.
+    /// 2. `[writable]` Destination SPL token account.
+    /// 3. `[]` PDA authority account.
+    /// 4. `[]` SPL Token program.
+    /// 5. `[writable]` Vesting schedule data account.
+    Release,
+
+    /// Transfer `amount` lamports of native SOL directly from the PDA-owned vault
+    /// account to the destination account, via direct lamport balance edits (the
+    /// System program cannot move lamports out of an account it doesn't own).
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` Source account (the program's PDA vault; owned by this program).
+    /// 1. `[writable]` Destination account.
+    TransferSol {
+        /// Amount of lamports to transfer.
+        amount: u64,
+    },
+}
+
+impl TransferInstruction {
+    /// Unpacks a byte buffer into a [`TransferInstruction`].
+    ///
+    /// The first byte selects the variant and the remaining bytes hold its payload,
+    /// mirroring the dispatch-on-first-byte convention used by `spl-token-swap`.
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (&tag, rest) = input
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+
+        Ok(match tag {
+            0 => {
+                let amount = rest
+                    .get(..8)
+                    .and_then(|slice| slice.try_into().ok())
+                    .map(u64::from_le_bytes)
+                    .ok_or(ProgramError::InvalidInstructionData)?;
+                let fee_bps = rest
+                    .get(8..10)
+                    .and_then(|slice| slice.try_into().ok())
+                    .map(u16::from_le_bytes)
+                    .ok_or(ProgramError::InvalidInstructionData)?;
+                Self::Transfer { amount, fee_bps }
+            }
+            1 => {
+                let count = rest
+                    .get(..4)
+                    .and_then(|slice| slice.try_into().ok())
+                    .map(u32::from_le_bytes)
+                    .ok_or(ProgramError::InvalidInstructionData)? as usize;
+                let body = rest.get(4..).ok_or(ProgramError::InvalidInstructionData)?;
+                if body.len() != count * 16 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+
+                let mut schedule = Vec::with_capacity(count);
+                for chunk in body.chunks_exact(16) {
+                    let release_time = u64::from_le_bytes(chunk[0..8].try_into().unwrap());
+                    let amount = u64::from_le_bytes(chunk[8..16].try_into().unwrap());
+                    schedule.push(ScheduleEntry {
+                        release_time,
+                        amount,
+                    });
+                }
+                Self::CreateSchedule { schedule }
+            }
+            2 => Self::Release,
+            3 => {
+                let amount = rest
+                    .get(..8)
+                    .and_then(|slice| slice.try_into().ok())
+                    .map(u64::from_le_bytes)
+                    .ok_or(ProgramError::InvalidInstructionData)?;
+                Self::TransferSol { amount }
+            }
+            _ => return Err(ProgramError::InvalidInstructionData),
+        })
+    }
+
+    /// Packs a [`TransferInstruction`] into its on-wire byte representation.
+    pub fn pack(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        match self {
+            Self::Transfer { amount, fee_bps } => {
+                buf.push(0);
+                buf.extend_from_slice(&amount.to_le_bytes());
+                buf.extend_from_slice(&fee_bps.to_le_bytes());
+            }
+            Self::CreateSchedule { schedule } => {
+                buf.push(1);
+                buf.extend_from_slice(&(schedule.len() as u32).to_le_bytes());
+                for entry in schedule {
+                    buf.extend_from_slice(&entry.release_time.to_le_bytes());
+                    buf.extend_from_slice(&entry.amount.to_le_bytes());
+                }
+            }
+            Self::Release => {
+                buf.push(2);
+            }
+            Self::TransferSol { amount } => {
+                buf.push(3);
+                buf.extend_from_slice(&amount.to_le_bytes());
+            }
+        }
+        buf
+    }
+}
+
+/// Creates a [`TransferInstruction::Transfer`] instruction.
+///
+/// The destination is always the wallet owner's associated token account for
+/// `mint_pubkey`, derived the same way the program itself will derive and validate it.
+/// Pass `fee_bps: 0` to skip the protocol fee; `fee_destination_pubkey` is ignored in
+/// that case but still included in the account list.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer(
+    program_id: &Pubkey,
+    source_pubkey: &Pubkey,
+    mint_pubkey: &Pubkey,
+    wallet_owner_pubkey: &Pubkey,
+    authority_pubkey: &Pubkey,
+    token_program_id: &Pubkey,
+    fee_destination_pubkey: &Pubkey,
+    amount: u64,
+    fee_bps: u16,
+) -> Instruction {
+    let data = TransferInstruction::Transfer { amount, fee_bps }.pack();
+    let destination_pubkey = get_associated_token_address(wallet_owner_pubkey, mint_pubkey);
+
+    let accounts = vec![
+        AccountMeta::new(*source_pubkey, false),
+        AccountMeta::new_readonly(*mint_pubkey, false),
+        AccountMeta::new(destination_pubkey, false),
+        AccountMeta::new_readonly(*authority_pubkey, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new(*wallet_owner_pubkey, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new(*fee_destination_pubkey, false),
+    ];
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    }
+}
+
+/// Creates a [`TransferInstruction::TransferSol`] instruction.
+///
+/// The source is always the program's PDA vault, derived the same way the program
+/// itself will derive and validate it.
+pub fn transfer_sol(program_id: &Pubkey, destination_pubkey: &Pubkey, amount: u64) -> Instruction {
+    let data = TransferInstruction::TransferSol { amount }.pack();
+    let (authority_pubkey, _bump_seed) = Pubkey::find_program_address(&[b"authority"], program_id);
+
+    let accounts = vec![
+        AccountMeta::new(authority_pubkey, false),
+        AccountMeta::new(*destination_pubkey, false),
+    ];
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    }
+}
+
+/// Creates a [`TransferInstruction::CreateSchedule`] instruction.
+pub fn create_schedule(
+    program_id: &Pubkey,
+    schedule_pubkey: &Pubkey,
+    schedule: Vec<ScheduleEntry>,
+) -> Instruction {
+    let data = TransferInstruction::CreateSchedule { schedule }.pack();
+
+    let accounts = vec![AccountMeta::new(*schedule_pubkey, false)];
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    }
+}
+
+/// Creates a [`TransferInstruction::Release`] instruction.
+pub fn release(
+    program_id: &Pubkey,
+    source_pubkey: &Pubkey,
+    mint_pubkey: &Pubkey,
+    destination_pubkey: &Pubkey,
+    authority_pubkey: &Pubkey,
+    token_program_id: &Pubkey,
+    schedule_pubkey: &Pubkey,
+) -> Instruction {
+    let data = TransferInstruction::Release.pack();
+
+    let accounts = vec![
+        AccountMeta::new(*source_pubkey, false),
+        AccountMeta::new_readonly(*mint_pubkey, false),
+        AccountMeta::new(*destination_pubkey, false),
+        AccountMeta::new_readonly(*authority_pubkey, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new(*schedule_pubkey, false),
+    ];
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    }
+}
@@ -0,0 +1,347 @@
+//! Instruction processing logic for the CPI_transfer program.
+
+use {
+    crate::{
+        error::TransferError,
+        instruction::TransferInstruction,
+        state::{ScheduleEntry, VestingSchedule},
+        tools::spl_token::{get_spl_token_amount, get_spl_token_mint, transfer_spl_tokens_signed},
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        msg,
+        program::invoke,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+    spl_associated_token_account::{get_associated_token_address, instruction::create_associated_token_account},
+};
+
+/// Processes an instruction for the CPI_transfer program.
+///
+/// # Parameters
+/// - `program_id`: The public key of the program.
+/// - `accounts`: The accounts required for the instruction.
+/// - `instruction_data`: The serialized [`TransferInstruction`].
+///
+/// # Returns
+/// - `ProgramResult`: Ok(()) on success, or an error value on failure.
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let instruction = TransferInstruction::unpack(instruction_data)?;
+
+    match instruction {
+        TransferInstruction::Transfer { amount, fee_bps } => {
+            process_transfer(program_id, accounts, amount, fee_bps)
+        }
+        TransferInstruction::CreateSchedule { schedule } => {
+            process_create_schedule(program_id, accounts, schedule)
+        }
+        TransferInstruction::Release => process_release(program_id, accounts),
+        TransferInstruction::TransferSol { amount } => {
+            process_transfer_sol(program_id, accounts, amount)
+        }
+    }
+}
+
+/// Transfers `amount` tokens from the PDA-owned source account to the wallet owner's
+/// associated token account for the mint, creating that associated token account first
+/// if it doesn't exist yet. If `fee_bps` is non-zero, a `fee_bps` / 10_000 cut of
+/// `amount` is routed to the fee destination account atomically alongside the transfer.
+///
+/// Accounts expected, in order:
+///     1. Source token account (must be owned by the PDA authority)
+///     2. Mint account
+///     3. Destination token account (the wallet owner's ATA for the mint)
+///     4. PDA authority account (must match PDA derived from seeds)
+///     5. SPL Token program account
+///     6. Wallet owner account (signer; funds creation of the ATA)
+///     7. System program account
+///     8. Associated Token Account program account
+///     9. Rent sysvar account
+///     10. Fee destination token account (same mint; ignored if `fee_bps` is zero)
+fn process_transfer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    fee_bps: u16,
+) -> ProgramResult {
+    // Create an iterator over the provided accounts.
+    let account_info_iter = &mut accounts.iter();
+
+    // 1. Source SPL token account (owned by the PDA authority).
+    let source_info = next_account_info(account_info_iter)?;
+    // 2. The Mint account for the SPL token.
+    let mint_info = next_account_info(account_info_iter)?;
+    // 3. Destination SPL token account (will receive tokens).
+    let destination_info = next_account_info(account_info_iter)?;
+    // 4. PDA authority account (must match derived PDA).
+    let authority_info = next_account_info(account_info_iter)?;
+    // 5. SPL token program account.
+    let token_program_info = next_account_info(account_info_iter)?;
+    // 6. Wallet owner account (signs and pays for ATA creation, if needed).
+    let wallet_owner_info = next_account_info(account_info_iter)?;
+    // 7. System program account.
+    let system_program_info = next_account_info(account_info_iter)?;
+    // 8. Associated Token Account program account.
+    let ata_program_info = next_account_info(account_info_iter)?;
+    // 9. Rent sysvar account.
+    let rent_sysvar_info = next_account_info(account_info_iter)?;
+    // 10. Fee destination token account.
+    let fee_destination_info = next_account_info(account_info_iter)?;
+
+    // Derive the expected PDA authority using the seed "authority" and the program_id.
+    let (expected_authority, bump_seed) = Pubkey::find_program_address(&[b"authority"], program_id);
+
+    // Ensure the provided authority account matches the derived PDA.
+    if expected_authority != *authority_info.key {
+        msg!("Invalid PDA authority provided.");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // Ensure the provided destination is the wallet owner's canonical ATA for the mint.
+    let expected_destination = get_associated_token_address(wallet_owner_info.key, mint_info.key);
+    if expected_destination != *destination_info.key {
+        msg!("Destination account is not the wallet owner's associated token account.");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // Create the destination ATA if it doesn't exist yet.
+    if destination_info.data_is_empty() {
+        msg!("Destination ATA does not exist; creating it.");
+        invoke(
+            &create_associated_token_account(
+                wallet_owner_info.key,
+                wallet_owner_info.key,
+                mint_info.key,
+                token_program_info.key,
+            ),
+            &[
+                wallet_owner_info.clone(),
+                destination_info.clone(),
+                wallet_owner_info.clone(),
+                mint_info.clone(),
+                system_program_info.clone(),
+                token_program_info.clone(),
+                rent_sysvar_info.clone(),
+                ata_program_info.clone(),
+            ],
+        )?;
+    }
+
+    // Confirm the source account is a valid SPL token account.
+    let _source_amount = get_spl_token_amount(source_info)?;
+
+    // Compute the fee and the remainder that go to the primary destination.
+    let fee = amount
+        .checked_mul(fee_bps as u64)
+        .and_then(|scaled| scaled.checked_div(10_000))
+        .ok_or(TransferError::FeeCalculationOverflow)?;
+    let remainder = amount
+        .checked_sub(fee)
+        .ok_or(TransferError::FeeCalculationOverflow)?;
+
+    if fee > 0 && get_spl_token_mint(fee_destination_info)? != *mint_info.key {
+        msg!("Fee destination account mint does not match the transfer mint.");
+        return Err(TransferError::FeeMintMismatch.into());
+    }
+
+    // Prepare the PDA authority seeds for signature.
+    let authority_seeds: &[&[u8]] = &[b"authority", &[bump_seed]];
+
+    // Log the transfer attempt.
+    msg!(
+        "Transferring {} tokens (fee: {}) from {} to {} using PDA {}",
+        amount,
+        fee,
+        source_info.key,
+        destination_info.key,
+        authority_info.key,
+    );
+
+    // Route the fee to the fee destination first, then the remainder to the primary
+    // destination. Both CPIs are invoked from within this single instruction, so they
+    // succeed or fail atomically.
+    if fee > 0 {
+        transfer_spl_tokens_signed(
+            token_program_info,
+            source_info,
+            mint_info,
+            fee_destination_info,
+            authority_info,
+            authority_seeds,
+            fee,
+        )?;
+    }
+
+    transfer_spl_tokens_signed(
+        token_program_info,
+        source_info,
+        mint_info,
+        destination_info,
+        authority_info,
+        authority_seeds,
+        remainder,
+    )?;
+
+    // Indicate success.
+    msg!("Transfer complete.");
+    Ok(())
+}
+
+/// Initializes a vesting schedule data account with an ordered list of release entries.
+///
+/// Accounts expected, in order:
+///     1. Vesting schedule data account (owned by this program, pre-allocated and not
+///        yet initialized)
+fn process_create_schedule(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    schedule: Vec<ScheduleEntry>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let schedule_info = next_account_info(account_info_iter)?;
+
+    if schedule_info.owner != program_id {
+        msg!("Vesting schedule account is not owned by this program.");
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let existing = VestingSchedule::unpack(&schedule_info.try_borrow_data()?)?;
+    if existing.is_initialized {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let vesting_schedule = VestingSchedule {
+        is_initialized: true,
+        released_count: 0,
+        schedule,
+    };
+    let packed = vesting_schedule.pack();
+
+    if packed.len() > schedule_info.data_len() {
+        msg!("Vesting schedule account is too small for the requested schedule.");
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+
+    schedule_info.try_borrow_mut_data()?[..packed.len()].copy_from_slice(&packed);
+
+    msg!(
+        "Vesting schedule created with {} entries.",
+        vesting_schedule.schedule.len()
+    );
+    Ok(())
+}
+
+/// Releases every vesting entry whose `release_time` has passed, transferring their
+/// combined amount from the PDA-owned source account to the destination account.
+///
+/// Accounts expected, in order:
+///     1. Source token account (must be owned by the PDA authority)
+///     2. Mint account
+///     3. Destination token account
+///     4. PDA authority account (must match PDA derived from seeds)
+///     5. SPL Token program account
+///     6. Vesting schedule data account
+fn process_release(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let source_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let schedule_info = next_account_info(account_info_iter)?;
+
+    let (expected_authority, bump_seed) = Pubkey::find_program_address(&[b"authority"], program_id);
+    if expected_authority != *authority_info.key {
+        msg!("Invalid PDA authority provided.");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if schedule_info.owner != program_id {
+        msg!("Vesting schedule account is not owned by this program.");
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let mut vesting_schedule = VestingSchedule::unpack(&schedule_info.try_borrow_data()?)?;
+    if !vesting_schedule.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let now: u64 = Clock::get()?
+        .unix_timestamp
+        .try_into()
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+
+    let (amount, released_count) = vesting_schedule.due_amount(now);
+    if amount == 0 {
+        msg!("No vesting schedule entries are due for release yet.");
+        return Err(TransferError::NothingDue.into());
+    }
+
+    let authority_seeds: &[&[u8]] = &[b"authority", &[bump_seed]];
+
+    transfer_spl_tokens_signed(
+        token_program_info,
+        source_info,
+        mint_info,
+        destination_info,
+        authority_info,
+        authority_seeds,
+        amount,
+    )?;
+
+    vesting_schedule.released_count = released_count;
+    let packed = vesting_schedule.pack();
+    schedule_info.try_borrow_mut_data()?[..packed.len()].copy_from_slice(&packed);
+
+    msg!("Released {} vested tokens.", amount);
+    Ok(())
+}
+
+/// Transfers `amount` lamports of native SOL directly from the PDA-owned vault account
+/// to the destination account, via direct lamport balance edits.
+///
+/// Accounts expected, in order:
+///     1. Source account (the program's PDA vault; must match PDA derived from seeds)
+///     2. Destination account
+fn process_transfer_sol(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let source_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+
+    // Derive the expected PDA authority using the seed "authority" and the program_id.
+    let (expected_authority, _bump_seed) = Pubkey::find_program_address(&[b"authority"], program_id);
+
+    // Ensure the provided source account matches the derived PDA; only the program can
+    // debit lamports from an account it owns, which is why this can't go through the
+    // System program.
+    if expected_authority != *source_info.key {
+        msg!("Invalid PDA authority provided.");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if **source_info.try_borrow_lamports()? < amount {
+        msg!("PDA vault has insufficient lamports for the requested transfer.");
+        return Err(TransferError::InsufficientFunds.into());
+    }
+
+    **source_info.try_borrow_mut_lamports()? -= amount;
+    **destination_info.try_borrow_mut_lamports()? += amount;
+
+    msg!(
+        "Transferred {} lamports from PDA {} to {}.",
+        amount,
+        source_info.key,
+        destination_info.key,
+    );
+    Ok(())
+}
@@ -0,0 +1,120 @@
+//! On-chain state definitions for the CPI_transfer program.
+
+use solana_program::program_error::ProgramError;
+
+/// Size in bytes of a packed [`ScheduleEntry`].
+pub const SCHEDULE_ENTRY_LEN: usize = 16;
+/// Size in bytes of the [`VestingSchedule`] header (`is_initialized` + `released_count`).
+pub const VESTING_HEADER_LEN: usize = 5;
+
+/// A single vesting schedule entry: an amount that unlocks at a given unix timestamp.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ScheduleEntry {
+    /// Unix timestamp (seconds) at which this entry becomes releasable.
+    pub release_time: u64,
+    /// Amount of tokens that unlock at `release_time`.
+    pub amount: u64,
+}
+
+impl ScheduleEntry {
+    fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        let release_time = data
+            .get(..8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let amount = data
+            .get(8..16)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        Ok(Self {
+            release_time,
+            amount,
+        })
+    }
+
+    fn pack(&self, dst: &mut Vec<u8>) {
+        dst.extend_from_slice(&self.release_time.to_le_bytes());
+        dst.extend_from_slice(&self.amount.to_le_bytes());
+    }
+}
+
+/// Vesting schedule state held in a data account owned by this program. Only the
+/// account's owner is checked; its address is not checked against a PDA derivation.
+///
+/// Layout: `is_initialized` (1 byte) | `released_count` (4 bytes, LE) | packed
+/// [`ScheduleEntry`] records, ordered by non-decreasing `release_time`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct VestingSchedule {
+    /// Whether this account has been initialized via `CreateSchedule`.
+    pub is_initialized: bool,
+    /// Number of leading `schedule` entries that have already been released.
+    pub released_count: u32,
+    /// Ordered list of vesting entries.
+    pub schedule: Vec<ScheduleEntry>,
+}
+
+impl VestingSchedule {
+    /// Unpacks a [`VestingSchedule`] from a data account's raw byte contents.
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < VESTING_HEADER_LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let is_initialized = data[0] != 0;
+        let released_count = u32::from_le_bytes(
+            data[1..VESTING_HEADER_LEN]
+                .try_into()
+                .map_err(|_| ProgramError::InvalidAccountData)?,
+        );
+
+        let mut schedule = Vec::new();
+        let mut offset = VESTING_HEADER_LEN;
+        while offset + SCHEDULE_ENTRY_LEN <= data.len() {
+            schedule.push(ScheduleEntry::unpack(&data[offset..offset + SCHEDULE_ENTRY_LEN])?);
+            offset += SCHEDULE_ENTRY_LEN;
+        }
+
+        Ok(Self {
+            is_initialized,
+            released_count,
+            schedule,
+        })
+    }
+
+    /// Packs this [`VestingSchedule`] into bytes suitable for writing into a data account.
+    pub fn pack(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::packed_len(self.schedule.len()));
+        data.push(self.is_initialized as u8);
+        data.extend_from_slice(&self.released_count.to_le_bytes());
+        for entry in &self.schedule {
+            entry.pack(&mut data);
+        }
+        data
+    }
+
+    /// Returns the number of bytes a [`VestingSchedule`] with `schedule_entries` entries packs to.
+    pub fn packed_len(schedule_entries: usize) -> usize {
+        VESTING_HEADER_LEN + schedule_entries * SCHEDULE_ENTRY_LEN
+    }
+
+    /// Sums the `amount` of every unreleased entry whose `release_time` is at or
+    /// before `now`, returning that total along with `released_count` advanced
+    /// past those entries.
+    pub fn due_amount(&self, now: u64) -> (u64, u32) {
+        let mut total = 0u64;
+        let mut released_count = self.released_count;
+
+        for entry in self.schedule.iter().skip(self.released_count as usize) {
+            if entry.release_time > now {
+                break;
+            }
+            total = total.saturating_add(entry.amount);
+            released_count += 1;
+        }
+
+        (total, released_count)
+    }
+}
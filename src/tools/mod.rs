@@ -0,0 +1,3 @@
+//! Reusable, program-agnostic helper modules.
+
+pub mod spl_token;
@@ -0,0 +1,57 @@
+//! SPL token CPI helpers shared by every instruction that moves tokens under the
+//! program's PDA authority, following the utility-function style used in
+//! `spl-governance`'s token tooling.
+
+use solana_program::{
+    account_info::AccountInfo, program::invoke_signed, program_error::ProgramError,
+    program_pack::Pack, pubkey::Pubkey,
+};
+use spl_token::{
+    instruction::transfer_checked,
+    state::{Account, Mint},
+};
+
+/// Transfers `amount` of `mint`'s tokens from `source` to `destination` via
+/// `spl_token::transfer_checked`, signed by the PDA `authority` derived from `seeds`.
+pub fn transfer_spl_tokens_signed<'a>(
+    token_program: &AccountInfo<'a>,
+    source: &AccountInfo<'a>,
+    mint: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    seeds: &[&[u8]],
+    amount: u64,
+) -> Result<(), ProgramError> {
+    let decimals = Mint::unpack(&mint.try_borrow_data()?)?.decimals;
+
+    invoke_signed(
+        &transfer_checked(
+            token_program.key,
+            source.key,
+            mint.key,
+            destination.key,
+            authority.key,
+            &[],
+            amount,
+            decimals,
+        )?,
+        &[
+            source.clone(),
+            mint.clone(),
+            destination.clone(),
+            authority.clone(),
+            token_program.clone(),
+        ],
+        &[seeds],
+    )
+}
+
+/// Unpacks `account` as an SPL token [`Account`] and returns its token amount.
+pub fn get_spl_token_amount(account: &AccountInfo) -> Result<u64, ProgramError> {
+    Ok(Account::unpack(&account.try_borrow_data()?)?.amount)
+}
+
+/// Unpacks `account` as an SPL token [`Account`] and returns the mint it belongs to.
+pub fn get_spl_token_mint(account: &AccountInfo) -> Result<Pubkey, ProgramError> {
+    Ok(Account::unpack(&account.try_borrow_data()?)?.mint)
+}
@@ -1,17 +1,19 @@
 // Integration test for the CPI_transfer program: verifies SPL token transfer via PDA authority.
 
-use CPI_transfer::process_instruction;
+use CPI_transfer::{instruction, process_instruction, state::{ScheduleEntry, VestingSchedule}};
 
 use {
     solana_program::{
-        instruction::{AccountMeta, Instruction},
         program_pack::Pack,
         pubkey::Pubkey,
         rent::Rent,
         system_instruction,
     },
     solana_program_test::{processor, ProgramTest, tokio},
-    solana_sdk::{signature::Signer, signer::keypair::Keypair, transaction::Transaction},
+    solana_sdk::{
+        account::Account as SolanaAccount, signature::Signer, signer::keypair::Keypair,
+        transaction::Transaction,
+    },
     spl_token::state::{Account, Mint},
     std::str::FromStr,
 };
@@ -26,10 +28,9 @@ async fn success() {
     // The program_id must match what the CPI_transfer program expects.
     let program_id = Pubkey::from_str("TransferTokens11111111111111111111111111111").unwrap();
 
-    // Generate keypairs for the source, mint, and destination token accounts.
+    // Generate keypairs for the source and mint accounts.
     let source = Keypair::new();
     let mint = Keypair::new();
-    let destination = Keypair::new();
 
     // Derive the PDA authority using the same seed as in the program logic.
     let (authority_pubkey, _) = Pubkey::find_program_address(&[b"authority"], &program_id);
@@ -98,10 +99,283 @@ async fn success() {
     );
     banks_client.process_transaction(transaction).await.unwrap();
 
+    // 3. Mint tokens to the source (PDA-owned) account.
+    let transaction = Transaction::new_signed_with_payer(
+        &[spl_token::instruction::mint_to(
+            &spl_token::id(),
+            &mint.pubkey(),
+            &source.pubkey(),
+            &payer.pubkey(),
+            &[],
+            amount,
+        )
+        .unwrap()],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    // 4. Construct and send the CPI_transfer instruction. The payer doubles as the
+    //    recipient wallet owner, so the program must create their ATA on the fly.
+    let destination = spl_associated_token_account::get_associated_token_address(
+        &payer.pubkey(),
+        &mint.pubkey(),
+    );
+    let fee_destination = Keypair::new();
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::transfer(
+            &program_id,
+            &source.pubkey(),
+            &mint.pubkey(),
+            &payer.pubkey(),
+            &authority_pubkey,
+            &spl_token::id(),
+            &fee_destination.pubkey(),
+            amount,
+            0,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    // Execute the transfer instruction.
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    // 5. Fetch the destination ATA and verify the tokens were transferred.
+    let account = banks_client
+        .get_account(destination)
+        .await
+        .unwrap()
+        .unwrap();
+    let token_account = Account::unpack(&account.data).unwrap();
+    assert_eq!(token_account.amount, amount);
+}
+
+/// This test simulates a transfer with a non-zero protocol fee, verifying that the
+/// fee and the remainder are both moved atomically: the fee destination receives
+/// `amount * fee_bps / 10_000` and the primary destination receives the rest.
+#[tokio::test]
+async fn transfer_with_fee() {
+    let program_id = Pubkey::from_str("TransferTokens11111111111111111111111111111").unwrap();
+
+    let source = Keypair::new();
+    let mint = Keypair::new();
+    let fee_destination = Keypair::new();
+
+    let (authority_pubkey, _) = Pubkey::find_program_address(&[b"authority"], &program_id);
+
+    let program_test = ProgramTest::new("CPI_transfer", program_id, processor!(process_instruction));
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let amount = 10_000;
+    let fee_bps = 100; // 1%
+    let decimals = 9;
+    let rent = Rent::default();
+
+    // 1. Create and initialize the SPL token mint.
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &payer.pubkey(),
+                &mint.pubkey(),
+                rent.minimum_balance(Mint::LEN),
+                Mint::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_mint(
+                &spl_token::id(),
+                &mint.pubkey(),
+                &payer.pubkey(),
+                None,
+                decimals,
+            )
+            .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+        &[&payer, &mint],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    // 2. Create and initialize the source account (owned by PDA authority).
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &payer.pubkey(),
+                &source.pubkey(),
+                rent.minimum_balance(Account::LEN),
+                Account::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_account(
+                &spl_token::id(),
+                &source.pubkey(),
+                &mint.pubkey(),
+                &authority_pubkey,
+            )
+            .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+        &[&payer, &source],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    // 3. Create and initialize the fee destination account.
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &payer.pubkey(),
+                &fee_destination.pubkey(),
+                rent.minimum_balance(Account::LEN),
+                Account::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_account(
+                &spl_token::id(),
+                &fee_destination.pubkey(),
+                &mint.pubkey(),
+                &payer.pubkey(),
+            )
+            .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+        &[&payer, &fee_destination],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    // 4. Mint tokens to the source (PDA-owned) account.
+    let transaction = Transaction::new_signed_with_payer(
+        &[spl_token::instruction::mint_to(
+            &spl_token::id(),
+            &mint.pubkey(),
+            &source.pubkey(),
+            &payer.pubkey(),
+            &[],
+            amount,
+        )
+        .unwrap()],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    // 5. Transfer with a 1% fee. The payer doubles as the recipient wallet owner.
+    let destination = spl_associated_token_account::get_associated_token_address(
+        &payer.pubkey(),
+        &mint.pubkey(),
+    );
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::transfer(
+            &program_id,
+            &source.pubkey(),
+            &mint.pubkey(),
+            &payer.pubkey(),
+            &authority_pubkey,
+            &spl_token::id(),
+            &fee_destination.pubkey(),
+            amount,
+            fee_bps,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    // 6. Verify the fee and remainder were both routed correctly.
+    let expected_fee = amount * fee_bps as u64 / 10_000;
+    let fee_account = banks_client
+        .get_account(fee_destination.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(Account::unpack(&fee_account.data).unwrap().amount, expected_fee);
+
+    let destination_account = banks_client.get_account(destination).await.unwrap().unwrap();
+    assert_eq!(
+        Account::unpack(&destination_account.data).unwrap().amount,
+        amount - expected_fee
+    );
+}
+
+/// This test simulates creating a vesting schedule with a single already-due entry
+/// and releasing it, verifying that the due amount (and only that amount) is
+/// transferred from the PDA-owned source account to the destination account.
+#[tokio::test]
+async fn release_due_schedule() {
+    let program_id = Pubkey::from_str("TransferTokens11111111111111111111111111111").unwrap();
+
+    let source = Keypair::new();
+    let mint = Keypair::new();
+    let destination = Keypair::new();
+    let schedule_account = Keypair::new();
+
+    let (authority_pubkey, _) = Pubkey::find_program_address(&[b"authority"], &program_id);
+
+    let program_test = ProgramTest::new("CPI_transfer", program_id, processor!(process_instruction));
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let amount = 10_000;
+    let decimals = 9;
+    let rent = Rent::default();
+
+    // 1. Create and initialize the SPL token mint.
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &payer.pubkey(),
+                &mint.pubkey(),
+                rent.minimum_balance(Mint::LEN),
+                Mint::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_mint(
+                &spl_token::id(),
+                &mint.pubkey(),
+                &payer.pubkey(),
+                None,
+                decimals,
+            )
+            .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+        &[&payer, &mint],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    // 2. Create and initialize the source account (owned by PDA authority).
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &payer.pubkey(),
+                &source.pubkey(),
+                rent.minimum_balance(Account::LEN),
+                Account::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_account(
+                &spl_token::id(),
+                &source.pubkey(),
+                &mint.pubkey(),
+                &authority_pubkey,
+            )
+            .unwrap(),
+        ],
+        Some(&payer.pubkey()),
+        &[&payer, &source],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+
     // 3. Create and initialize the destination account (owned by payer).
     let transaction = Transaction::new_signed_with_payer(
         &[
-            // Create the destination token account.
             system_instruction::create_account(
                 &payer.pubkey(),
                 &destination.pubkey(),
@@ -109,7 +383,6 @@ async fn success() {
                 Account::LEN as u64,
                 &spl_token::id(),
             ),
-            // Initialize the destination token account.
             spl_token::instruction::initialize_account(
                 &spl_token::id(),
                 &destination.pubkey(),
@@ -141,28 +414,51 @@ async fn success() {
     );
     banks_client.process_transaction(transaction).await.unwrap();
 
-    // 5. Construct and send the CPI_transfer instruction, using the correct account order.
+    // 5. Create the vesting schedule data account, owned by the program, with a single
+    //    entry whose `release_time` is already in the past.
+    let schedule_len = VestingSchedule::packed_len(1);
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &payer.pubkey(),
+                &schedule_account.pubkey(),
+                rent.minimum_balance(schedule_len),
+                schedule_len as u64,
+                &program_id,
+            ),
+            instruction::create_schedule(
+                &program_id,
+                &schedule_account.pubkey(),
+                vec![ScheduleEntry {
+                    release_time: 0,
+                    amount,
+                }],
+            ),
+        ],
+        Some(&payer.pubkey()),
+        &[&payer, &schedule_account],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    // 6. Release the due entry.
     let transaction = Transaction::new_signed_with_payer(
-        &[Instruction::new_with_bincode(
-            program_id,
-            &(), // No instruction data required for this program.
-            vec![
-                AccountMeta::new(source.pubkey(), false),                // Source token account.
-                AccountMeta::new_readonly(mint.pubkey(), false),         // Mint.
-                AccountMeta::new(destination.pubkey(), false),           // Destination token account.
-                AccountMeta::new_readonly(authority_pubkey, false),      // PDA authority.
-                AccountMeta::new_readonly(spl_token::id(), false),       // SPL Token program.
-            ],
+        &[instruction::release(
+            &program_id,
+            &source.pubkey(),
+            &mint.pubkey(),
+            &destination.pubkey(),
+            &authority_pubkey,
+            &spl_token::id(),
+            &schedule_account.pubkey(),
         )],
         Some(&payer.pubkey()),
         &[&payer],
         recent_blockhash,
     );
-
-    // Execute the transfer instruction.
     banks_client.process_transaction(transaction).await.unwrap();
 
-    // 6. Fetch the destination account and verify the tokens were transferred.
+    // 7. Fetch the destination account and verify the due amount was released.
     let account = banks_client
         .get_account(destination.pubkey())
         .await
@@ -171,3 +467,53 @@ async fn success() {
     let token_account = Account::unpack(&account.data).unwrap();
     assert_eq!(token_account.amount, amount);
 }
+
+/// This test simulates transferring native SOL out of the program's PDA vault via
+/// direct lamport manipulation, since the vault is owned by this program rather than
+/// the System program and so can't be moved with a System program CPI.
+#[tokio::test]
+async fn transfer_sol() {
+    let program_id = Pubkey::from_str("TransferTokens11111111111111111111111111111").unwrap();
+    let destination = Keypair::new();
+
+    let (authority_pubkey, _) = Pubkey::find_program_address(&[b"authority"], &program_id);
+
+    let amount = 5_000_000;
+    let vault_lamports = 10_000_000;
+
+    let mut program_test = ProgramTest::new("CPI_transfer", program_id, processor!(process_instruction));
+    // A PDA has no private key and so can't sign a `create_account`; pre-fund it
+    // directly as an account owned by this program instead.
+    program_test.add_account(
+        authority_pubkey,
+        SolanaAccount {
+            lamports: vault_lamports,
+            owner: program_id,
+            ..SolanaAccount::default()
+        },
+    );
+
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::transfer_sol(&program_id, &destination.pubkey(), amount)],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let vault_account = banks_client
+        .get_account(authority_pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(vault_account.lamports, vault_lamports - amount);
+
+    let destination_account = banks_client
+        .get_account(destination.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(destination_account.lamports, amount);
+}